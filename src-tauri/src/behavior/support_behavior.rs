@@ -1,11 +1,16 @@
-use std::time::{Instant, Duration};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use slog::Logger;
-use tauri::Window;
+use tauri::{async_runtime::JoinHandle, Window};
 
 use crate::{
+    events::{BotEvent, EventBus},
     image_analyzer::ImageAnalyzer,
-    ipc::{BotConfig, FrontendInfo, SlotType, SupportConfig},
+    ipc::{BotConfig, FrontendInfo, RuleContext, SlotType, SupportConfig},
+    journal::{JournalWriter, JournaledAction},
     movement::MovementAccessor,
     platform::send_slot_eval,
     play,
@@ -13,14 +18,66 @@ use crate::{
 
 use super::Behavior;
 
+/// Delay before we check whether a slot use actually produced its expected effect
+const SLOT_VERIFICATION_DELAY_MS: u128 = 500;
+
+/// How often the telemetry ring is drained and emitted to the frontend
+const TELEMETRY_FLUSH_INTERVAL_MS: u64 = 250;
+
+/// Direction a slot use is expected to move its tracked stat
+enum ExpectedEffect {
+    Rise,
+    Drop,
+}
+
+/// A `send_slot` waiting to be checked against `image.client_stats` for success/failure
+struct PendingSlotCheck {
+    slot_index: (usize, usize),
+    slot_type: SlotType,
+    baseline: u32,
+    sent_at: Instant,
+}
+
+/// Stat a given slot type is expected to affect, and in which direction
+fn expected_effect(slot_type: SlotType) -> Option<ExpectedEffect> {
+    match slot_type {
+        SlotType::Pill
+        | SlotType::Food
+        | SlotType::MpRestorer
+        | SlotType::FpRestorer
+        | SlotType::HealSkill => Some(ExpectedEffect::Rise),
+        SlotType::AttackSkill => Some(ExpectedEffect::Drop),
+        _ => None,
+    }
+}
+
+/// Read the `client_stats` value that `slot_type` is expected to affect
+fn sample_stat(slot_type: SlotType, image: &ImageAnalyzer) -> Option<u32> {
+    match slot_type {
+        SlotType::Pill | SlotType::Food => Some(image.client_stats.hp.value),
+        SlotType::MpRestorer => Some(image.client_stats.mp.value),
+        SlotType::FpRestorer => Some(image.client_stats.fp.value),
+        SlotType::HealSkill | SlotType::AttackSkill => Some(image.client_stats.target_hp.value),
+        _ => None,
+    }
+}
+
 pub struct SupportBehavior<'a> {
     movement: &'a MovementAccessor,
     window: &'a Window,
     slots_usage_last_time: [[Option<Instant>; 10]; 9],
-    last_buff_usage: Instant,
+    slot_tries: [[u8; 10]; 9],
+    slot_overflow: [[u32; 10]; 9],
+    slot_disabled_until: [[Option<Instant>; 10]; 9],
+    pending_slot_checks: Vec<PendingSlotCheck>,
+    rule_last_fired: Vec<Option<Instant>>,
     last_jump_time: Instant,
     avoid_obstacle_direction: String,
     last_far_from_target: Option<Instant>,
+    journal: Option<JournalWriter>,
+    events: Arc<EventBus>,
+    telemetry_task: Option<JoinHandle<()>>,
+    had_target: bool,
     //is_on_flight: bool,
 }
 
@@ -30,18 +87,66 @@ impl<'a> Behavior<'a> for SupportBehavior<'a> {
             movement,
             window,
             slots_usage_last_time: [[None; 10]; 9],
-            last_buff_usage: Instant::now(),
+            slot_tries: [[0; 10]; 9],
+            slot_overflow: [[0; 10]; 9],
+            slot_disabled_until: [[None; 10]; 9],
+            pending_slot_checks: Vec::new(),
+            rule_last_fired: Vec::new(),
             last_jump_time: Instant::now(),
             avoid_obstacle_direction: "D".to_owned(),
             last_far_from_target: None,
+            journal: None,
+            events: Arc::new(EventBus::default()),
+            telemetry_task: None,
+            had_target: false,
             //is_on_flight: false,
         }
     }
 
-    fn start(&mut self, _config: &BotConfig) {}
+    fn start(&mut self, config: &BotConfig) {
+        if config.journal_enabled() {
+            let path = config.journal_path();
+            let path = std::path::Path::new(&path);
+
+            // On startup, scan the journal left by a previous run (if any), validate its
+            // checksums, and truncate at the first corrupt/partially-written frame so the
+            // new session doesn't append after a crash-damaged tail.
+            if path.exists() {
+                if config.journal_resume_enabled() {
+                    let _ = crate::journal::replay(path, self.movement, self.window);
+                } else {
+                    let _ = crate::journal::recover(path);
+                }
+            }
+
+            self.journal = JournalWriter::create(path).ok();
+        }
+
+        // Drain and emit telemetry on its own cadence, independent of (and not blocking) the
+        // decision loop in `run_iteration`.
+        if self.telemetry_task.is_none() {
+            let events = Arc::clone(&self.events);
+            let window = self.window.clone();
+            self.telemetry_task = Some(tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(Duration::from_millis(TELEMETRY_FLUSH_INTERVAL_MS));
+                loop {
+                    interval.tick().await;
+                    crate::events::drain_and_emit(&events, &window);
+                }
+            }));
+        }
+    }
     fn update(&mut self, _config: &BotConfig) {}
     fn stop(&mut self, _config: &BotConfig) {
         self.slots_usage_last_time = [[None; 10]; 9];
+        self.slot_tries = [[0; 10]; 9];
+        self.slot_overflow = [[0; 10]; 9];
+        self.slot_disabled_until = [[None; 10]; 9];
+        self.pending_slot_checks.clear();
+        self.journal = None;
+        if let Some(task) = self.telemetry_task.take() {
+            task.abort();
+        }
     }
 
     fn run_iteration(
@@ -50,22 +155,41 @@ impl<'a> Behavior<'a> for SupportBehavior<'a> {
         config: &BotConfig,
         image: &mut ImageAnalyzer,
     ) {
+        let buff_cooldown_ms = config.interval_between_buffs() as u64;
         let config = config.support_config();
         let target_marker = image.identify_target_marker(true);
         self.update_slots_usage(config);
+        self.verify_pending_slot_checks(config, image);
+
+        self.events.push(BotEvent::StatSnapshot {
+            hp: image.client_stats.hp.value,
+            mp: image.client_stats.mp.value,
+            fp: image.client_stats.fp.value,
+            target_hp: image.client_stats.target_hp.value,
+        });
+        if target_marker.is_some() && !self.had_target {
+            if let Some(marker) = target_marker {
+                self.events.push(BotEvent::TargetAcquired {
+                    distance: image.get_target_marker_distance(marker),
+                });
+            }
+        }
+        self.had_target = target_marker.is_some();
 
         if image.client_stats.target_hp.value == 0 && target_marker.is_some() {
-            self.get_slot_for(config, None, SlotType::RezSkill, true);
+            self.get_slot_for(config, None, SlotType::RezSkill, true, image);
             self.slots_usage_last_time = [[None; 10]; 9];
             return;
         }
 
-        self.check_restorations(config, image);
-        std::thread::sleep(Duration::from_millis(100));
+        let marker_distance = target_marker.map(|marker| image.get_target_marker_distance(marker));
+        if let Some(distance) = marker_distance {
+            self.events.push(BotEvent::Distance(distance));
+        }
+        self.run_rules(config, image, marker_distance, buff_cooldown_ms);
 
         if image.client_stats.target_hp.value > 0 {
-            if let Some(target_marker) = target_marker {
-                let marker_distance = image.get_target_marker_distance(target_marker);
+            if let Some(marker_distance) = marker_distance {
                 if marker_distance > 200 {
                     if self.last_far_from_target.is_none() {
                         self.last_far_from_target = Some(Instant::now());
@@ -73,7 +197,6 @@ impl<'a> Behavior<'a> for SupportBehavior<'a> {
                     self.avoid_obstacle(config);
                 } else {
                     self.last_far_from_target = None;
-                    self.check_buffs(config);
                 }
             } else {
                 self.avoid_obstacle(config);
@@ -84,6 +207,20 @@ impl<'a> Behavior<'a> for SupportBehavior<'a> {
 
 impl<'a> SupportBehavior<'_> {
 
+    /// Append an emitted action to the journal, if one is active
+    fn journal_action(&mut self, action: JournaledAction) {
+        if let Some(journal) = self.journal.as_mut() {
+            let _ = journal.append(action);
+        }
+    }
+
+    /// Telemetry ring. Drained independently of the decision loop by the background task
+    /// spawned in `start`; exposed for callers that want to inspect pending events directly
+    /// instead of waiting on the `bot-events` emission.
+    pub fn events(&self) -> &EventBus {
+        &self.events
+    }
+
     fn avoid_obstacle(&mut self, config: &SupportConfig) {
         if let Some(last_far_from_target) = self.last_far_from_target {
             if last_far_from_target.elapsed().as_millis() > config.obstacle_avoidance_cooldown() {
@@ -94,6 +231,10 @@ impl<'a> SupportBehavior<'_> {
             play!(self.movement => [
                 PressKey("Z"),
             ]);
+            self.journal_action(JournaledAction::PressKey { key: "Z".to_owned() });
+            self.events.push(BotEvent::ObstacleAvoidanceStep {
+                direction: "Z".to_owned(),
+            });
         }
     }
 
@@ -111,6 +252,22 @@ impl<'a> SupportBehavior<'_> {
             Wait(dur::Fixed(300)),
         ]);
 
+        self.journal_action(JournaledAction::HoldKeys {
+            keys: vec!["W".to_owned(), "Space".to_owned(), self.avoid_obstacle_direction.clone()],
+        });
+        self.journal_action(JournaledAction::Wait { duration_ms: 200 });
+        self.journal_action(JournaledAction::ReleaseKey { key: self.avoid_obstacle_direction.clone() });
+        self.journal_action(JournaledAction::Wait { duration_ms: 500 });
+        self.journal_action(JournaledAction::ReleaseKeys {
+            keys: vec!["Space".to_owned(), "W".to_owned()],
+        });
+        self.journal_action(JournaledAction::HoldKeyFor { key: "S".to_owned(), duration_ms: 50 });
+        self.journal_action(JournaledAction::PressKey { key: "Z".to_owned() });
+        self.journal_action(JournaledAction::Wait { duration_ms: 300 });
+        self.events.push(BotEvent::ObstacleAvoidanceStep {
+            direction: self.avoid_obstacle_direction.clone(),
+        });
+
         self.avoid_obstacle_direction = {
             if self.avoid_obstacle_direction == "D" {
                 "A".to_owned()
@@ -150,13 +307,17 @@ impl<'a> SupportBehavior<'_> {
         threshold: Option<u32>,
         slot_type: SlotType,
         send: bool,
+        image: &ImageAnalyzer,
     ) -> Option<(usize, usize)> {
-        if let Some(slot_index) =
-            config.get_usable_slot_index(slot_type, threshold, self.slots_usage_last_time)
-        {
+        if let Some(slot_index) = config.get_usable_slot_index(
+            slot_type,
+            threshold,
+            self.slots_usage_last_time,
+            self.slot_disabled_until,
+        ) {
             if send {
                 //slog::debug!(self.logger, "Slot usage"; "slot_type" => slot_type.to_string(), "value" => threshold);
-                self.send_slot(slot_index);
+                self.send_slot(slot_index, slot_type, image);
             }
 
             return Some(slot_index);
@@ -164,49 +325,123 @@ impl<'a> SupportBehavior<'_> {
         return None;
     }
 
-    fn send_slot(&mut self, slot_index: (usize, usize)) {
+    fn send_slot(&mut self, slot_index: (usize, usize), slot_type: SlotType, image: &ImageAnalyzer) {
         // Send keystroke for first slot mapped to pill
         send_slot_eval(self.window, slot_index.0, slot_index.1);
         // Update usage last time
         self.slots_usage_last_time[slot_index.0][slot_index.1] = Some(Instant::now());
-    }
+        self.journal_action(JournaledAction::SendSlot {
+            bar: slot_index.0,
+            index: slot_index.1,
+        });
+        self.events.push(BotEvent::SlotUsed {
+            bar: slot_index.0,
+            index: slot_index.1,
+        });
 
-    fn check_buffs(&mut self, config: &SupportConfig) {
-        if self.last_buff_usage.elapsed().as_millis() > config.interval_between_buffs() {
-            self.last_buff_usage = Instant::now();
-            self.get_slot_for(config, None, SlotType::BuffSkill, true);
-            std::thread::sleep(Duration::from_millis(100));
+        // Schedule a check of whether this use had its expected effect
+        if let Some(baseline) = sample_stat(slot_type, image) {
+            self.pending_slot_checks.push(PendingSlotCheck {
+                slot_index,
+                slot_type,
+                baseline,
+                sent_at: Instant::now(),
+            });
         }
     }
 
-    fn check_restorations(&mut self, config: &SupportConfig, image: &mut ImageAnalyzer) {
-        // Check HP
-        let stat = Some(image.client_stats.hp.value);
-        if image.client_stats.hp.value > 0 {
-            if self
-                .get_slot_for(config, stat, SlotType::Pill, true)
-                .is_none()
-            {
-                self.get_slot_for(config, stat, SlotType::Food, true);
+    /// Sample due pending slot checks and update each slot's tries/backoff state
+    fn verify_pending_slot_checks(&mut self, config: &SupportConfig, image: &ImageAnalyzer) {
+        let (due, pending): (Vec<_>, Vec<_>) = self
+            .pending_slot_checks
+            .drain(..)
+            .partition(|check| check.sent_at.elapsed().as_millis() >= SLOT_VERIFICATION_DELAY_MS);
+        self.pending_slot_checks = pending;
+
+        for check in due {
+            let (bar, index) = check.slot_index;
+            let succeeded = match (expected_effect(check.slot_type), sample_stat(check.slot_type, image)) {
+                (Some(ExpectedEffect::Rise), Some(current)) => current > check.baseline,
+                (Some(ExpectedEffect::Drop), Some(current)) => current < check.baseline,
+                _ => true,
+            };
+
+            if succeeded {
+                self.slot_tries[bar][index] = 0;
+                self.slot_overflow[bar][index] = 0;
+                self.slot_disabled_until[bar][index] = None;
+                continue;
             }
-        }
 
-        //Check target HP
-        let stat = Some(image.client_stats.target_hp.value);
-        if image.client_stats.target_hp.value > 0 {
-            self.get_slot_for(config, stat, SlotType::HealSkill, true);
+            self.slot_tries[bar][index] += 1;
+            if self.slot_tries[bar][index] > config.max_slot_tries() {
+                let overflow = self.slot_overflow[bar][index];
+                let backoff_ms = config.slot_backoff_base_ms() * 2u64.pow(overflow);
+                self.slot_disabled_until[bar][index] =
+                    Some(Instant::now() + Duration::from_millis(backoff_ms));
+                self.slot_overflow[bar][index] = overflow.saturating_add(1);
+                self.slot_tries[bar][index] = 0;
+            }
         }
+    }
 
-        // Check MP
-        let stat = Some(image.client_stats.mp.value);
-        if image.client_stats.mp.value > 0 {
-            self.get_slot_for(config, stat, SlotType::MpRestorer, true);
+    /// Evaluate the configured rules top-down and fire the first (or every, if
+    /// `fire_all_eligible_rules` is set) rule whose conditions hold and whose cooldown has
+    /// elapsed. A rule whose action finds no usable slot doesn't count as fired. Rules sharing
+    /// a `fallback_group` stay mutually exclusive regardless of `fire_all_eligible_rules`, so
+    /// e.g. the default Pill/Food rule pair falls through to Food only when no Pill slot is
+    /// usable, instead of firing both in the same tick.
+    fn run_rules(
+        &mut self,
+        config: &SupportConfig,
+        image: &ImageAnalyzer,
+        marker_distance: Option<u32>,
+        buff_cooldown_ms: u64,
+    ) {
+        let rules = config.rules(buff_cooldown_ms);
+        if self.rule_last_fired.len() != rules.len() {
+            self.rule_last_fired = vec![None; rules.len()];
         }
 
-        // Check FP
-        let stat = Some(image.client_stats.fp.value);
-        if image.client_stats.fp.value > 0 {
-            self.get_slot_for(config, stat, SlotType::FpRestorer, true);
+        let mut fired_groups: Vec<u8> = Vec::new();
+
+        for (index, rule) in rules.iter().enumerate() {
+            if let Some(group) = rule.fallback_group() {
+                if fired_groups.contains(&group) {
+                    continue;
+                }
+            }
+
+            let context = RuleContext {
+                hp: Some(image.client_stats.hp.value),
+                mp: Some(image.client_stats.mp.value),
+                fp: Some(image.client_stats.fp.value),
+                target_hp: Some(image.client_stats.target_hp.value),
+                target_marker_distance: marker_distance,
+                time_since_last_use_ms: self.rule_last_fired[index]
+                    .map(|last_fired| last_fired.elapsed().as_millis() as u32),
+            };
+
+            if !rule.matches(&context) {
+                continue;
+            }
+
+            let on_cooldown = self.rule_last_fired[index]
+                .map_or(false, |last_fired| last_fired.elapsed().as_millis() < rule.cooldown_ms().into());
+            if on_cooldown {
+                continue;
+            }
+
+            let threshold = sample_stat(rule.action(), image);
+            if self.get_slot_for(config, threshold, rule.action(), true, image).is_some() {
+                self.rule_last_fired[index] = Some(Instant::now());
+                if let Some(group) = rule.fallback_group() {
+                    fired_groups.push(group);
+                }
+                if !config.fire_all_eligible_rules() {
+                    break;
+                }
+            }
         }
     }
 }
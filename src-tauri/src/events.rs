@@ -0,0 +1,95 @@
+//! Lock-free telemetry bus decoupling decision loops from the frontend.
+//!
+//! Behaviors push typed events into an `EventBus` without ever blocking; the Tauri `Window`
+//! side drains and emits them to the frontend on its own cadence. When the ring is full a
+//! push simply overwrites the oldest, never-drained entry in that slot rather than stalling
+//! the bot, so decision latency stops being coupled to how fast the UI can keep up.
+
+use std::{
+    ptr,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+
+use serde::Serialize;
+use tauri::Window;
+
+/// Default number of in-flight events the ring can hold before it starts overwriting
+const DEFAULT_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Serialize)]
+pub enum BotEvent {
+    SlotUsed { bar: usize, index: usize },
+    TargetAcquired { distance: u32 },
+    Distance(u32),
+    StatSnapshot { hp: u32, mp: u32, fp: u32, target_hp: u32 },
+    ObstacleAvoidanceStep { direction: String },
+}
+
+/// A bounded, lock-free MPSC ring buffer of `BotEvent`s
+pub struct EventBus {
+    slots: Vec<AtomicPtr<BotEvent>>,
+    capacity: usize,
+    write_cursor: AtomicUsize,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        let slots = (0..capacity).map(|_| AtomicPtr::new(ptr::null_mut())).collect();
+        Self {
+            slots,
+            capacity,
+            write_cursor: AtomicUsize::new(0),
+        }
+    }
+
+    /// Push an event without blocking. If the slot it lands on is still occupied (the ring
+    /// is full and the consumer hasn't drained yet), the old, un-drained event is discarded.
+    pub fn push(&self, event: BotEvent) {
+        let index = self.write_cursor.fetch_add(1, Ordering::Relaxed) % self.capacity;
+        let boxed = Box::into_raw(Box::new(event));
+        let previous = self.slots[index].swap(boxed, Ordering::AcqRel);
+        if !previous.is_null() {
+            unsafe { drop(Box::from_raw(previous)) };
+        }
+    }
+
+    /// Drain every currently queued event, leaving the ring empty. Order reflects slot
+    /// position rather than strict push order, which is fine for telemetry.
+    pub fn drain(&self) -> Vec<BotEvent> {
+        let mut events = Vec::new();
+        for slot in self.slots.iter() {
+            let ptr = slot.swap(ptr::null_mut(), Ordering::AcqRel);
+            if !ptr.is_null() {
+                events.push(*unsafe { Box::from_raw(ptr) });
+            }
+        }
+        events
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl Drop for EventBus {
+    fn drop(&mut self) {
+        for slot in self.slots.iter() {
+            let ptr = slot.swap(ptr::null_mut(), Ordering::AcqRel);
+            if !ptr.is_null() {
+                unsafe { drop(Box::from_raw(ptr)) };
+            }
+        }
+    }
+}
+
+/// Drain `bus` and emit the batch to the frontend; called from the Tauri window's own
+/// cadence rather than from inside a behavior's decision loop.
+pub fn drain_and_emit(bus: &EventBus, window: &Window) {
+    let events = bus.drain();
+    if events.is_empty() {
+        return;
+    }
+    let _ = window.emit("bot-events", &events);
+}
@@ -61,12 +61,13 @@ impl SlotBar {
             .position(|slot| slot.slot_type == slot_type)
     }
 
-    /// Get a random usable matching slot index
+    /// Get the highest-priority usable matching slot index, falling back to the lowest threshold
     pub fn get_usable_slot_index(
         &self,
         slot_type: SlotType,
         threshold: Option<u32>,
         last_slots_usage: [[Option<Instant>; 10]; 9],
+        disabled_until: [[Option<Instant>; 10]; 9],
         slot_bar_index: usize,
     ) -> Option<(usize, usize)> {
         self.slots()
@@ -77,8 +78,14 @@ impl SlotBar {
                     && slot.slot_enabled
                     && slot.slot_threshold.unwrap_or(100) >= threshold.unwrap_or(0)
                     && last_slots_usage[slot_bar_index][*index].is_none()
+                    && disabled_until[slot_bar_index][*index]
+                        .map_or(true, |until| Instant::now() >= until)
+            })
+            .min_by(|x, y| {
+                x.1.get_slot_priority()
+                    .cmp(&y.1.get_slot_priority())
+                    .then(x.1.slot_threshold.cmp(&y.1.slot_threshold))
             })
-            .min_by(|x, y| x.1.slot_threshold.cmp(&y.1.slot_threshold))
             //.choose(rng)
             .map(|(index, _)| (slot_bar_index, index))
     }
@@ -90,6 +97,8 @@ pub struct Slot {
     slot_cooldown: Option<u32>,
     slot_threshold: Option<u32>,
     slot_enabled: bool,
+    /// Lower values are tried first when several slots match (e.g. use the cheap pill before the expensive one)
+    slot_priority: Option<u8>,
 }
 
 impl Default for Slot {
@@ -99,6 +108,7 @@ impl Default for Slot {
             slot_cooldown: None,
             slot_threshold: None,
             slot_enabled: true,
+            slot_priority: None,
         }
     }
 }
@@ -111,6 +121,167 @@ impl Slot {
         }
         return Some(100);
     }
+
+    pub fn get_slot_priority(&self) -> u8 {
+        self.slot_priority.unwrap_or(15)
+    }
+}
+
+/// A `client_stats` field (or derived value) a rule condition can compare against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatField {
+    Hp,
+    Mp,
+    Fp,
+    TargetHp,
+    TargetMarkerDistance,
+    /// Milliseconds since the owning rule last fired
+    TimeSinceLastUseMs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparator {
+    GreaterThan,
+    GreaterOrEqual,
+    LessThan,
+    LessOrEqual,
+    Equal,
+}
+
+impl Comparator {
+    fn apply(&self, actual: u32, expected: u32) -> bool {
+        match self {
+            Comparator::GreaterThan => actual > expected,
+            Comparator::GreaterOrEqual => actual >= expected,
+            Comparator::LessThan => actual < expected,
+            Comparator::LessOrEqual => actual <= expected,
+            Comparator::Equal => actual == expected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Combinator {
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RuleCondition {
+    field: StatField,
+    comparator: Comparator,
+    value: u32,
+}
+
+impl RuleCondition {
+    pub fn new(field: StatField, comparator: Comparator, value: u32) -> Self {
+        Self {
+            field,
+            comparator,
+            value,
+        }
+    }
+
+    fn matches(&self, context: &RuleContext) -> bool {
+        match context.value_for(self.field) {
+            Some(actual) => self.comparator.apply(actual, self.value),
+            None => false,
+        }
+    }
+}
+
+/// Snapshot of the values a rule's conditions may be evaluated against on a given tick
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuleContext {
+    pub hp: Option<u32>,
+    pub mp: Option<u32>,
+    pub fp: Option<u32>,
+    pub target_hp: Option<u32>,
+    pub target_marker_distance: Option<u32>,
+    pub time_since_last_use_ms: Option<u32>,
+}
+
+impl RuleContext {
+    fn value_for(&self, field: StatField) -> Option<u32> {
+        match field {
+            StatField::Hp => self.hp,
+            StatField::Mp => self.mp,
+            StatField::Fp => self.fp,
+            StatField::TargetHp => self.target_hp,
+            StatField::TargetMarkerDistance => self.target_marker_distance,
+            StatField::TimeSinceLastUseMs => self.time_since_last_use_ms,
+        }
+    }
+}
+
+/// A condition -> action rule: if `conditions` (joined by `combinator`) hold and the rule isn't
+/// on its own cooldown, `action` is attempted through the usual slot-selection machinery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rule {
+    name: Option<String>,
+    #[serde(default = "Rule::default_combinator")]
+    combinator: Combinator,
+    conditions: Vec<RuleCondition>,
+    action: SlotType,
+    cooldown_ms: Option<u64>,
+    priority: Option<u8>,
+    /// Rules sharing a `fallback_group` are mutually exclusive within a tick: once one of
+    /// them fires, the rest of the group is skipped for that tick regardless of
+    /// `fire_all_eligible_rules`. Mirrors the legacy "try Pill, else Food" fallback chains.
+    fallback_group: Option<u8>,
+}
+
+impl Rule {
+    fn default_combinator() -> Combinator {
+        Combinator::And
+    }
+
+    pub fn new(conditions: Vec<RuleCondition>, action: SlotType, priority: u8) -> Self {
+        Self {
+            name: None,
+            combinator: Combinator::And,
+            conditions,
+            action,
+            cooldown_ms: None,
+            priority: Some(priority),
+            fallback_group: None,
+        }
+    }
+
+    pub fn with_cooldown(mut self, cooldown_ms: u64) -> Self {
+        self.cooldown_ms = Some(cooldown_ms);
+        self
+    }
+
+    /// Marks this rule as a fallback alternative within `group`: only the first rule in the
+    /// group whose action finds a usable slot fires on a given tick, the rest are skipped.
+    pub fn with_fallback_group(mut self, group: u8) -> Self {
+        self.fallback_group = Some(group);
+        self
+    }
+
+    pub fn matches(&self, context: &RuleContext) -> bool {
+        match self.combinator {
+            Combinator::And => self.conditions.iter().all(|c| c.matches(context)),
+            Combinator::Or => self.conditions.iter().any(|c| c.matches(context)),
+        }
+    }
+
+    pub fn action(&self) -> SlotType {
+        self.action
+    }
+
+    pub fn priority(&self) -> u8 {
+        self.priority.unwrap_or(15)
+    }
+
+    pub fn cooldown_ms(&self) -> u64 {
+        self.cooldown_ms.unwrap_or(0)
+    }
+
+    pub fn fallback_group(&self) -> Option<u8> {
+        self.fallback_group
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -145,10 +316,36 @@ pub struct FarmingConfig {
     is_manual_targetting: Option<bool>,
 
     obstacle_avoidance_max_try: Option<u32>,
+
+    /// Consecutive failed attempts allowed on a slot before it's temporarily disabled
+    max_slot_tries: Option<u8>,
+
+    /// Base backoff duration (ms) applied the first time a slot is disabled; doubles on each repeat offense
+    slot_backoff_base_ms: Option<u64>,
+
+    /// Condition -> action rules evaluated each tick, in priority order
+    rules: Option<Vec<Rule>>,
+
+    /// Fire every matching rule each tick instead of stopping at the first one that fires
+    fire_all_eligible_rules: Option<bool>,
 }
 
 impl FarmingConfig {
 
+    /// Rules evaluated each tick, in priority order
+    pub fn rules(&self) -> Vec<Rule> {
+        let mut rules = self.rules.clone().unwrap_or_default();
+        rules.sort_by_key(Rule::priority);
+        rules
+    }
+
+    /// Defaults to true: the legacy hardcoded checks ran HP/target HP/MP/FP/buffs
+    /// unconditionally every tick, so independent slot categories must not short-circuit
+    /// each other just because one of them already fired this tick.
+    pub fn fire_all_eligible_rules(&self) -> bool {
+        self.fire_all_eligible_rules.unwrap_or(true)
+    }
+
     pub fn circle_pattern_rotation_duration(&self) -> u64 {
         self.circle_pattern_rotation_duration.unwrap_or(30)
     }
@@ -182,18 +379,22 @@ impl FarmingConfig {
         None
     }
 
-    /// Get a random usable matching slot index
+    /// Get a usable matching slot index from the first slot bar that has one; within that bar
+    /// the highest-priority slot wins, ties going to the lowest threshold. Priority is not
+    /// compared across bars.
     pub fn get_usable_slot_index(
         &self,
         slot_type: SlotType,
         threshold: Option<u32>,
         last_slots_usage: [[Option<Instant>; 10]; 9],
+        disabled_until: [[Option<Instant>; 10]; 9],
     ) -> Option<(usize, usize)> {
         for n in 0..9 {
             let found_index = self.slot_bars()[n].get_usable_slot_index(
                 slot_type,
                 threshold,
                 last_slots_usage,
+                disabled_until,
                 n,
             );
             if found_index.is_some() {
@@ -203,6 +404,16 @@ impl FarmingConfig {
         None
     }
 
+    /// Number of consecutive failed attempts allowed on a slot before it's temporarily disabled
+    pub fn max_slot_tries(&self) -> u8 {
+        self.max_slot_tries.unwrap_or(3)
+    }
+
+    /// Base backoff duration (ms) applied the first time a slot is disabled; doubles on each repeat offense
+    pub fn slot_backoff_base_ms(&self) -> u64 {
+        self.slot_backoff_base_ms.unwrap_or(5000)
+    }
+
     pub fn is_manual_targetting(&self) -> bool {
         self.is_manual_targetting.unwrap_or(false)
     }
@@ -215,9 +426,89 @@ impl FarmingConfig {
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SupportConfig {
     slot_bars: Option<[SlotBar; 9]>,
+
+    /// Consecutive failed attempts allowed on a slot before it's temporarily disabled
+    max_slot_tries: Option<u8>,
+
+    /// Base backoff duration (ms) applied the first time a slot is disabled; doubles on each repeat offense
+    slot_backoff_base_ms: Option<u64>,
+
+    /// Condition -> action rules evaluated each tick, in priority order; falls back to
+    /// `Self::default_rules` so existing configs keep behaving the way they always have.
+    rules: Option<Vec<Rule>>,
+
+    /// Fire every matching rule each tick instead of stopping at the first one that fires
+    fire_all_eligible_rules: Option<bool>,
 }
 
+/// Fallback group shared by the default Pill/Food rules: the legacy code only reached for
+/// Food when no Pill slot was usable, so the rules must stay mutually exclusive per tick
+/// even when `fire_all_eligible_rules` is on.
+const PILL_FOOD_FALLBACK_GROUP: u8 = 0;
+
 impl SupportConfig {
+    /// Mirrors the historical hardcoded `check_restorations`/`check_buffs` behavior
+    fn default_rules(buff_cooldown_ms: u64) -> Vec<Rule> {
+        vec![
+            Rule::new(
+                vec![RuleCondition::new(StatField::Hp, Comparator::GreaterThan, 0)],
+                SlotType::Pill,
+                10,
+            )
+            .with_fallback_group(PILL_FOOD_FALLBACK_GROUP),
+            Rule::new(
+                vec![RuleCondition::new(StatField::Hp, Comparator::GreaterThan, 0)],
+                SlotType::Food,
+                11,
+            )
+            .with_fallback_group(PILL_FOOD_FALLBACK_GROUP),
+            Rule::new(
+                vec![RuleCondition::new(StatField::TargetHp, Comparator::GreaterThan, 0)],
+                SlotType::HealSkill,
+                20,
+            ),
+            Rule::new(
+                vec![RuleCondition::new(StatField::Mp, Comparator::GreaterThan, 0)],
+                SlotType::MpRestorer,
+                30,
+            ),
+            Rule::new(
+                vec![RuleCondition::new(StatField::Fp, Comparator::GreaterThan, 0)],
+                SlotType::FpRestorer,
+                40,
+            ),
+            Rule::new(
+                vec![RuleCondition::new(
+                    StatField::TargetMarkerDistance,
+                    Comparator::LessOrEqual,
+                    200,
+                )],
+                SlotType::BuffSkill,
+                90,
+            )
+            .with_cooldown(buff_cooldown_ms),
+        ]
+    }
+
+    /// Rules evaluated each tick, in priority order. `buff_cooldown_ms` is only used to build
+    /// the fallback default rule set, so a customized `BotConfig::interval_between_buffs`
+    /// keeps applying for users who haven't configured explicit `rules`.
+    pub fn rules(&self, buff_cooldown_ms: u64) -> Vec<Rule> {
+        let mut rules = self
+            .rules
+            .clone()
+            .unwrap_or_else(|| Self::default_rules(buff_cooldown_ms));
+        rules.sort_by_key(Rule::priority);
+        rules
+    }
+
+    /// Defaults to true: the legacy hardcoded checks ran HP/target HP/MP/FP/buffs
+    /// unconditionally every tick, so independent slot categories must not short-circuit
+    /// each other just because one of them already fired this tick.
+    pub fn fire_all_eligible_rules(&self) -> bool {
+        self.fire_all_eligible_rules.unwrap_or(true)
+    }
+
 
     pub fn slot_bars(&self) -> Vec<SlotBar> {
         self.slot_bars
@@ -233,18 +524,22 @@ impl SupportConfig {
         return self.slots(slot_bar_index)[slot_index].get_slot_cooldown();
     }
 
-    /// Get a random usable matching slot index
+    /// Get a usable matching slot index from the first slot bar that has one; within that bar
+    /// the highest-priority slot wins, ties going to the lowest threshold. Priority is not
+    /// compared across bars.
     pub fn get_usable_slot_index(
         &self,
         slot_type: SlotType,
         threshold: Option<u32>,
         last_slots_usage: [[Option<Instant>; 10]; 9],
+        disabled_until: [[Option<Instant>; 10]; 9],
     ) -> Option<(usize, usize)> {
         for n in 0..9 {
             let found_index = self.slot_bars()[n].get_usable_slot_index(
                 slot_type,
                 threshold,
                 last_slots_usage,
+                disabled_until,
                 n,
             );
             if found_index.is_some() {
@@ -253,6 +548,16 @@ impl SupportConfig {
         }
         None
     }
+
+    /// Number of consecutive failed attempts allowed on a slot before it's temporarily disabled
+    pub fn max_slot_tries(&self) -> u8 {
+        self.max_slot_tries.unwrap_or(3)
+    }
+
+    /// Base backoff duration (ms) applied the first time a slot is disabled; doubles on each repeat offense
+    pub fn slot_backoff_base_ms(&self) -> u64 {
+        self.slot_backoff_base_ms.unwrap_or(5000)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -282,7 +587,14 @@ pub struct BotConfig {
     inactivity_timeout: Option<u64>,
     obstacle_avoidance_cooldown: Option<u64>,
     whitelist_enabled: Option<bool>,
-    whitelist: Option<Vec<(u32, u32, String)>>
+    whitelist: Option<Vec<(u32, u32, String)>>,
+
+    /// Record every emitted action to a write-ahead journal file
+    journal_enabled: Option<bool>,
+    journal_path: Option<String>,
+    /// Replay the recovered journal through the behavior on startup, instead of just
+    /// validating/truncating it before resuming normal operation
+    journal_resume: Option<bool>,
 
 }
 
@@ -310,6 +622,10 @@ impl Default for BotConfig {
             obstacle_avoidance_cooldown: Some(0),
             whitelist_enabled: Some(false),
             whitelist: None,
+
+            journal_enabled: Some(false),
+            journal_path: None,
+            journal_resume: Some(false),
         }
     }
 }
@@ -389,6 +705,20 @@ impl BotConfig {
         return self.whitelist_enabled.unwrap_or(false);
     }
 
+    pub fn journal_enabled(&self) -> bool {
+        self.journal_enabled.unwrap_or(false)
+    }
+
+    pub fn journal_path(&self) -> String {
+        self.journal_path
+            .clone()
+            .unwrap_or_else(|| "bot_journal.log".to_owned())
+    }
+
+    pub fn journal_resume_enabled(&self) -> bool {
+        self.journal_resume.unwrap_or(false)
+    }
+
     pub fn match_whitelist(&self, target: Target) -> bool {
         if let Some(whitelist) = self.whitelist.clone() {
             let mut result = false;
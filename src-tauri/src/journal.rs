@@ -0,0 +1,300 @@
+//! Write-ahead journal of every action emitted by a behavior.
+//!
+//! Records are appended as framed, checksummed fragments (mirroring the
+//! classic LevelDB log format) so a process killed mid-write leaves a
+//! detectable, truncatable tail instead of a corrupt file. `replay` re-feeds
+//! a recorded session back through `MovementAccessor`/`send_slot_eval`,
+//! honoring the original inter-action timing, so a good farming/support
+//! rotation can be captured once and replayed on demand.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Read, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::Window;
+
+use crate::{movement::MovementAccessor, platform::send_slot_eval, play};
+
+/// Fragments are packed into fixed-size blocks so a reader can resync after corruption
+const BLOCK_SIZE: usize = 32 * 1024;
+/// crc32 (4) + payload length (2) + fragment type (1)
+const HEADER_SIZE: usize = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FragmentType {
+    Full = 1,
+    First = 2,
+    Middle = 3,
+    Last = 4,
+}
+
+impl FragmentType {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::Full),
+            2 => Some(Self::First),
+            3 => Some(Self::Middle),
+            4 => Some(Self::Last),
+            _ => None,
+        }
+    }
+}
+
+/// A single emitted action, recorded relative to the start of the journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalRecord {
+    pub timestamp_ms: u64,
+    pub action: JournaledAction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JournaledAction {
+    SendSlot { bar: usize, index: usize },
+    PressKey { key: String },
+    HoldKeys { keys: Vec<String> },
+    ReleaseKey { key: String },
+    ReleaseKeys { keys: Vec<String> },
+    HoldKeyFor { key: String, duration_ms: u64 },
+    Wait { duration_ms: u64 },
+}
+
+/// CRC32 (IEEE 802.3) bitwise implementation, kept local to avoid a new dependency
+fn crc32(bytes: &[u8]) -> u32 {
+    const POLY: u32 = 0xEDB88320;
+    let mut crc = 0xFFFFFFFFu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = 0u32.wrapping_sub(crc & 1);
+            crc = (crc >> 1) ^ (POLY & mask);
+        }
+    }
+    !crc
+}
+
+/// Appends journal records to a file, splitting oversized payloads across ring fragments
+pub struct JournalWriter {
+    file: File,
+    block_offset: usize,
+    started_at: Instant,
+}
+
+impl JournalWriter {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        // Resuming an existing file: fragments must line up with the reader's absolute-offset
+        // block math, so pick up mid-block rather than assuming we're starting a fresh block.
+        let existing_len = file.metadata()?.len() as usize;
+
+        // Continue the journal-wide clock rather than restarting it at zero, so timestamps
+        // (and therefore `replay`'s inter-record delays) stay correct across a crash/resume
+        // boundary instead of collapsing the gap between sessions to 0ms.
+        let elapsed_before_this_session = if existing_len > 0 {
+            JournalReader::open(path)?
+                .read_all()
+                .last()
+                .map(|record| record.timestamp_ms)
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
+        Ok(Self {
+            file,
+            block_offset: existing_len % BLOCK_SIZE,
+            started_at: Instant::now() - Duration::from_millis(elapsed_before_this_session),
+        })
+    }
+
+    pub fn append(&mut self, action: JournaledAction) -> io::Result<()> {
+        let record = JournalRecord {
+            timestamp_ms: self.started_at.elapsed().as_millis() as u64,
+            action,
+        };
+        let payload = serde_json::to_vec(&record).unwrap_or_default();
+        self.write_fragmented(&payload)
+    }
+
+    fn write_fragmented(&mut self, payload: &[u8]) -> io::Result<()> {
+        let max_fragment = BLOCK_SIZE - HEADER_SIZE;
+        let mut offset = 0;
+        let mut first = true;
+
+        while offset < payload.len() || (first && payload.is_empty()) {
+            let remaining_in_block = BLOCK_SIZE.saturating_sub(self.block_offset);
+            if remaining_in_block < HEADER_SIZE {
+                // Not enough room for even a header; pad out the rest of the block
+                self.file.write_all(&vec![0u8; remaining_in_block])?;
+                self.block_offset = 0;
+                continue;
+            }
+
+            let remaining_payload = payload.len() - offset;
+            let space = remaining_in_block - HEADER_SIZE;
+            let chunk_len = remaining_payload.min(space.min(max_fragment));
+            let chunk = &payload[offset..offset + chunk_len];
+            let is_last_chunk = offset + chunk_len >= payload.len();
+
+            let fragment_type = match (first, is_last_chunk) {
+                (true, true) => FragmentType::Full,
+                (true, false) => FragmentType::First,
+                (false, true) => FragmentType::Last,
+                (false, false) => FragmentType::Middle,
+            };
+
+            self.write_fragment(fragment_type, chunk)?;
+            self.block_offset += HEADER_SIZE + chunk_len;
+            offset += chunk_len;
+            first = false;
+        }
+
+        self.file.flush()
+    }
+
+    fn write_fragment(&mut self, fragment_type: FragmentType, chunk: &[u8]) -> io::Result<()> {
+        let mut frame = Vec::with_capacity(HEADER_SIZE + chunk.len());
+        frame.extend_from_slice(&crc32(chunk).to_le_bytes());
+        frame.extend_from_slice(&(chunk.len() as u16).to_le_bytes());
+        frame.push(fragment_type as u8);
+        frame.extend_from_slice(chunk);
+        self.file.write_all(&frame)
+    }
+}
+
+/// Scans a journal file, validates fragment checksums, and stops at the first corrupt or
+/// truncated frame rather than failing the whole read.
+pub struct JournalReader {
+    bytes: Vec<u8>,
+}
+
+impl JournalReader {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        Ok(Self { bytes })
+    }
+
+    pub fn read_all(&self) -> Vec<JournalRecord> {
+        self.scan().0
+    }
+
+    /// Scans every fragment, returning the parsed records and the byte offset of the first
+    /// corrupt or incomplete frame (i.e. the length the file should be truncated to).
+    fn scan(&self) -> (Vec<JournalRecord>, usize) {
+        let mut records = Vec::new();
+        let mut pending: Vec<u8> = Vec::new();
+        let mut cursor = 0;
+
+        while cursor + HEADER_SIZE <= self.bytes.len() {
+            let block_end = ((cursor / BLOCK_SIZE) + 1) * BLOCK_SIZE;
+            if cursor + HEADER_SIZE > block_end || cursor + HEADER_SIZE > self.bytes.len() {
+                cursor = block_end;
+                continue;
+            }
+
+            let crc = u32::from_le_bytes(self.bytes[cursor..cursor + 4].try_into().unwrap());
+            let len = u16::from_le_bytes(self.bytes[cursor + 4..cursor + 6].try_into().unwrap()) as usize;
+            let Some(fragment_type) = FragmentType::from_u8(self.bytes[cursor + 6]) else {
+                break;
+            };
+
+            let payload_start = cursor + HEADER_SIZE;
+            let payload_end = payload_start + len;
+            if payload_end > self.bytes.len() || payload_end > block_end {
+                break;
+            }
+
+            let chunk = &self.bytes[payload_start..payload_end];
+            if crc32(chunk) != crc {
+                // Partially written tail: discard it and stop here
+                break;
+            }
+
+            match fragment_type {
+                FragmentType::Full => {
+                    pending.clear();
+                    pending.extend_from_slice(chunk);
+                    if let Ok(record) = serde_json::from_slice(&pending) {
+                        records.push(record);
+                    }
+                    pending.clear();
+                }
+                FragmentType::First => {
+                    pending.clear();
+                    pending.extend_from_slice(chunk);
+                }
+                FragmentType::Middle => {
+                    pending.extend_from_slice(chunk);
+                }
+                FragmentType::Last => {
+                    pending.extend_from_slice(chunk);
+                    if let Ok(record) = serde_json::from_slice(&pending) {
+                        records.push(record);
+                    }
+                    pending.clear();
+                }
+            }
+
+            cursor = payload_end;
+        }
+
+        (records, cursor)
+    }
+}
+
+/// Scans the journal at `path`, validates every fragment's checksum, and truncates the file
+/// at the first corrupt or incomplete frame (e.g. a tail left by a process killed mid-write).
+/// Returns the records that validated cleanly, so a caller can optionally resume them.
+pub fn recover(path: &Path) -> io::Result<Vec<JournalRecord>> {
+    let (records, valid_len) = JournalReader::open(path)?.scan();
+    let file = OpenOptions::new().write(true).open(path)?;
+    file.set_len(valid_len as u64)?;
+    Ok(records)
+}
+
+/// Re-executes a recorded session through `MovementAccessor`/`send_slot_eval`, honoring the
+/// original inter-action timing. Runs the same corrupt-tail recovery as `recover`.
+pub fn replay(path: &Path, movement: &MovementAccessor, window: &Window) -> io::Result<()> {
+    use crate::movement::prelude::*;
+
+    let records = recover(path)?;
+    let mut last_timestamp_ms = 0u64;
+
+    for record in records {
+        let delay = record.timestamp_ms.saturating_sub(last_timestamp_ms);
+        if delay > 0 {
+            std::thread::sleep(Duration::from_millis(delay));
+        }
+        last_timestamp_ms = record.timestamp_ms;
+
+        match record.action {
+            JournaledAction::SendSlot { bar, index } => send_slot_eval(window, bar, index),
+            JournaledAction::PressKey { key } => play!(movement => [PressKey(&key)]),
+            JournaledAction::HoldKeys { keys } => {
+                let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+                play!(movement => [HoldKeys(keys)]);
+            }
+            JournaledAction::ReleaseKey { key } => play!(movement => [ReleaseKey(&key)]),
+            JournaledAction::ReleaseKeys { keys } => {
+                let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+                play!(movement => [ReleaseKeys(keys)]);
+            }
+            JournaledAction::HoldKeyFor { key, duration_ms } => {
+                play!(movement => [HoldKeyFor(&key, dur::Fixed(duration_ms))]);
+            }
+            JournaledAction::Wait { duration_ms } => {
+                play!(movement => [Wait(dur::Fixed(duration_ms))]);
+            }
+        }
+    }
+
+    Ok(())
+}